@@ -0,0 +1,56 @@
+//! Runtime support for `#[derive(MultiVersion)]`.
+//!
+//! This crate is split from `multi_version_derive` the same way `strum` is
+//! split from `strum_macros`: a `proc-macro = true` crate is only allowed
+//! to export its macros, so the trait the derive implements has to live
+//! here instead, in a crate downstream code can actually depend on.
+
+pub use multi_version_derive::MultiVersion;
+
+/// A type whose variants each carry a lifecycle (introduced/deprecated) and
+/// a wire representation across a sequence of semver-versioned protocol
+/// revisions.
+///
+/// `#[derive(MultiVersion)]` generates an implementation of this trait
+/// instead of a bag of inherent methods, so that generic code can be
+/// written against a `T: MultiVersion` bound the same way `strum` lets
+/// callers write `T: IntoEnumIterator` instead of depending on a concrete
+/// enum's inherent methods.
+pub trait MultiVersion: Sized + Copy {
+    /// The type each variant is represented as on the wire, e.g. `u8`.
+    type Discriminant;
+
+    /// The version this variant was introduced in.
+    fn implemented_since(&self) -> semver::Version;
+
+    /// The version this variant was removed in, if it has been.
+    fn deprecated_since(&self) -> Option<semver::Version>;
+
+    /// Whether this variant is valid for the given protocol version.
+    fn exists_in(&self, version: &semver::Version) -> bool;
+
+    /// The wire value this variant takes on for the given version, or
+    /// `None` if it doesn't exist in that version.
+    fn value_for_version(&self, version: &semver::Version) -> Option<Self::Discriminant>;
+
+    /// All variants that exist in the given version, excluding any listed
+    /// in `skip`.
+    fn get_all_values(version: &semver::Version, skip: Option<&[Self]>) -> Vec<Self>;
+
+    /// The inverse of [`value_for_version`](MultiVersion::value_for_version):
+    /// finds the variant that maps to `value` for the given version.
+    ///
+    /// A variant's wire value isn't necessarily static across versions (see
+    /// `alternative_version`), so this can't be a lookup table. Instead it
+    /// walks every variant that exists in `version` and returns the first
+    /// one whose own `value_for_version` matches. This is the version-aware
+    /// analogue of `strum`'s `FromRepr`.
+    fn from_value_for_version(value: Self::Discriminant, version: &semver::Version) -> Option<Self>
+    where
+        Self::Discriminant: PartialEq,
+    {
+        Self::get_all_values(version, None)
+            .into_iter()
+            .find(|candidate| candidate.value_for_version(version).as_ref() == Some(&value))
+    }
+}
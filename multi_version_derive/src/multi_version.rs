@@ -0,0 +1,306 @@
+use super::properties::HasMultiVersionVariantProperties;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, PathArguments, Type, TypeParen};
+
+pub fn derive_multi_version_inner(ast: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let gen = &ast.generics;
+    let attrs = &ast.attrs;
+
+    let mut discriminant_type: Type = syn::parse("usize".parse().unwrap()).unwrap();
+
+    for attr in attrs {
+        let path = &attr.path;
+        let tokens = &attr.tokens;
+        if path.leading_colon.is_some() {
+            continue;
+        }
+        if path.segments.len() != 1 {
+            continue;
+        }
+        let segment = path.segments.first().unwrap();
+        if segment.ident != "repr" {
+            continue;
+        }
+        if !matches!(segment.arguments, PathArguments::None) {
+            continue;
+        }
+        let typ_paren = match syn::parse2::<Type>(tokens.clone()) {
+            Ok(Type::Paren(TypeParen { elem, .. })) => *elem,
+            _ => continue,
+        };
+        let inner_path = match &typ_paren {
+            Type::Path(t) => t,
+            _ => continue,
+        };
+        if let Some(seg) = inner_path.path.segments.last() {
+            for t in &[
+                "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+            ] {
+                if seg.ident == t {
+                    discriminant_type = typ_paren;
+                    break;
+                }
+            }
+        }
+    }
+
+    if gen.lifetimes().count() > 0 {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "This macro doesn't support enums with lifetimes. \
+             The resulting enums would be unbounded.",
+        ));
+    }
+
+    let variants = match &ast.data {
+        Data::Enum(v) => &v.variants,
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "This macro only supports enums.",
+            ))
+        }
+    };
+
+    let mut implemented_arms = Vec::new();
+    let mut deprecated_arms = Vec::new();
+    let mut value_arms = Vec::new();
+    let mut to_string_arms = Vec::new();
+    let mut name_arms = Vec::new();
+    let mut from_str_checks = Vec::new();
+    let mut variant_idents = Vec::new();
+    for variant in variants {
+        let variant_properties = variant.get_variant_properties()?;
+
+        variant_idents.push(variant.ident.clone());
+        let variant_ident = variant.ident.clone();
+
+        let ident_str = variant_ident.to_string();
+
+        let name_str = match &variant_properties.to_string {
+            Some(to_string) => to_string.value(),
+            None => ident_str.clone(),
+        };
+        to_string_arms.push(quote! { #name::#variant_ident => #name_str });
+
+        let mut historical_name = quote! { #ident_str };
+        for (req, alt_name) in variant_properties.alternate_names.iter().rev() {
+            let req_str = req.value();
+            let alt_name_str = alt_name.value();
+            historical_name = quote! {
+                if semver::VersionReq::from_str(#req_str).unwrap().matches(version) {
+                    #alt_name_str
+                } else {
+                    #historical_name
+                }
+            };
+        }
+        name_arms.push(quote! { #name::#variant_ident => #historical_name });
+
+        let mut aliases: Vec<String> = variant_properties
+            .serialize
+            .iter()
+            .map(|lit| lit.value())
+            .collect();
+        if let Some(to_string) = &variant_properties.to_string {
+            aliases.push(to_string.value());
+        }
+        from_str_checks.push(quote! {
+            if (#name::#variant_ident).exists_in(version)
+                && (Some(s) == (#name::#variant_ident).name_for_version(version) #(|| s == #aliases)*)
+            {
+                return Some(#name::#variant_ident);
+            }
+        });
+
+        if let Some(implemented) = &variant_properties.implemented {
+            let version_str = implemented.value();
+            implemented_arms.push(quote! {
+                #name::#variant_ident => semver::Version::from_str(#version_str).unwrap()
+            });
+        }
+
+        if let Some(deprecated) = &variant_properties.deprecated {
+            let version_str = deprecated.value();
+            deprecated_arms.push(quote! {
+                #name::#variant_ident => Some(semver::Version::from_str(#version_str).unwrap())
+            });
+        }
+
+        if !variant_properties.alternate_versions.is_empty() {
+            let mut arm = quote! { #discriminant_type::from(*self) };
+
+            for (req, value) in variant_properties.alternate_versions.iter().rev() {
+                let req_str = req.value();
+                arm = quote! {
+                    if semver::VersionReq::from_str(#req_str).unwrap().matches(version) {
+                        #discriminant_type::from(#value)
+                    } else {
+                        #arm
+                    }
+                };
+            }
+
+            value_arms.push(quote! { #name::#variant_ident => #arm });
+        }
+    }
+
+    implemented_arms.push(quote! { _ => semver::Version::new(0, 0, 0) });
+    deprecated_arms.push(quote! { _ => None });
+    value_arms.push(quote! { _ => #discriminant_type::from(*self) });
+
+    let all_variants = quote! { [
+        #(#name::#variant_idents),*
+    ] };
+    let variant_count = variant_idents.len();
+    let iter_ident = format_ident!("{}VersionIter", name);
+
+    Ok(quote! {
+        impl ::multi_version::MultiVersion for #name {
+            type Discriminant = #discriminant_type;
+
+            #[inline]
+            fn implemented_since (&self) -> semver::Version
+            {
+                match self {
+                    #(#implemented_arms),*
+                }
+            }
+
+            #[inline]
+            fn deprecated_since (&self) -> Option<semver::Version>
+            {
+                match self {
+                    #(#deprecated_arms),*
+                }
+            }
+
+            #[inline]
+            fn value_for_version (&self, version: &semver::Version) -> Option<Self::Discriminant>
+            {
+                if self.exists_in(version) {
+                Some (match self {
+                    #(#value_arms),*
+                })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn exists_in (&self, version: &semver::Version) -> bool
+            {
+                *version >= self.implemented_since() && {
+                    if let Some(depricated) = self.deprecated_since() {
+                        *version < depricated
+                    } else {
+                        true
+                    }
+                }
+            }
+
+            #[inline]
+            fn get_all_values (version: &semver::Version, skip: Option<&[Self]>) -> Vec<Self>
+            {
+                Self::iter_for_version(version, skip).collect()
+            }
+        }
+
+        /// Lazily iterates over the variants that exist in a given
+        /// version, without allocating. Produced by `iter_for_version`.
+        pub struct #iter_ident<'a> {
+            variants: [#name; #variant_count],
+            cursor: usize,
+            version: semver::Version,
+            skip: Option<&'a [#name]>,
+        }
+
+        impl<'a> Iterator for #iter_ident<'a> {
+            type Item = #name;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                use ::multi_version::MultiVersion;
+
+                while self.cursor < self.variants.len() {
+                    let variant = self.variants[self.cursor];
+                    self.cursor += 1;
+
+                    if self.skip.unwrap_or(&[]).contains(&variant) {
+                        continue;
+                    }
+
+                    if variant.exists_in(&self.version) {
+                        return Some(variant);
+                    }
+                }
+
+                None
+            }
+        }
+
+        impl #name {
+            /// The name this variant serializes to for the given version,
+            /// or `None` if the variant doesn't exist in that version.
+            #[inline]
+            pub fn to_string_for_version(&self, version: &semver::Version) -> Option<String> {
+                use ::multi_version::MultiVersion;
+
+                if !self.exists_in(version) {
+                    return None;
+                }
+
+                Some(
+                    match self {
+                        #(#to_string_arms),*
+                    }
+                    .to_owned(),
+                )
+            }
+
+            /// The name this variant was known by in the given version,
+            /// falling back to its current ident if it was never renamed.
+            #[inline]
+            pub fn name_for_version(&self, version: &semver::Version) -> Option<&'static str> {
+                use ::multi_version::MultiVersion;
+
+                if !self.exists_in(version) {
+                    return None;
+                }
+
+                Some(match self {
+                    #(#name_arms),*
+                })
+            }
+
+            /// Parses `s` back into a variant, accepting any of its
+            /// `serialize` aliases or its name, restricted to variants that
+            /// exist in the given version.
+            #[inline]
+            pub fn from_str_for_version(s: &str, version: &semver::Version) -> Option<Self> {
+                use ::multi_version::MultiVersion;
+
+                #(#from_str_checks)*
+
+                None
+            }
+
+            /// Iterates over the variants that exist in `version`,
+            /// skipping any listed in `skip`, without allocating a `Vec`.
+            #[inline]
+            pub fn iter_for_version<'a>(
+                version: &semver::Version,
+                skip: Option<&'a [Self]>,
+            ) -> #iter_ident<'a> {
+                #iter_ident {
+                    variants: #all_variants,
+                    cursor: 0,
+                    version: version.clone(),
+                    skip,
+                }
+            }
+        }
+    })
+}
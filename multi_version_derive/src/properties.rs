@@ -0,0 +1,367 @@
+use proc_macro2::Span;
+use semver::{Op, Version, VersionReq};
+use std::default::Default;
+use std::str::FromStr;
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, LitInt, LitStr, Token, Variant,
+};
+
+pub mod kw {
+    use syn::custom_keyword;
+
+    // variant metadata
+    custom_keyword!(implemented);
+    custom_keyword!(deprecated);
+    custom_keyword!(alternative_version);
+    custom_keyword!(alternative_name);
+    custom_keyword!(serialize);
+    custom_keyword!(to_string);
+}
+
+pub enum VariantMeta {
+    Implemented {
+        kw: kw::implemented,
+        value: LitStr,
+    },
+    Deprecated {
+        kw: kw::deprecated,
+        value: LitStr,
+    },
+    AlternativeVersion {
+        kw: kw::alternative_version,
+        versions: Vec<(LitStr, LitInt)>,
+    },
+    AlternativeName {
+        kw: kw::alternative_name,
+        names: Vec<(LitStr, LitStr)>,
+    },
+    Serialize {
+        kw: kw::serialize,
+        value: LitStr,
+    },
+    ToString {
+        kw: kw::to_string,
+        value: LitStr,
+    },
+}
+
+impl Parse for VariantMeta {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::implemented) {
+            let kw = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let value = input.parse()?;
+            Ok(VariantMeta::Implemented { kw, value })
+        } else if lookahead.peek(kw::deprecated) {
+            let kw = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let value = input.parse()?;
+            Ok(VariantMeta::Deprecated { kw, value })
+        } else if lookahead.peek(kw::alternative_version) {
+            let kw = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let versions = content.parse_terminated::<_, Token![,]>(VersionLit::parse)?;
+            Ok(VariantMeta::AlternativeVersion {
+                kw,
+                versions: versions
+                    .into_iter()
+                    .map(|VersionLit(version, value)| (version, value))
+                    .collect(),
+            })
+        } else if lookahead.peek(kw::alternative_name) {
+            let kw = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let names = content.parse_terminated::<_, Token![,]>(Name::parse)?;
+            Ok(VariantMeta::AlternativeName {
+                kw,
+                names: names
+                    .into_iter()
+                    .map(|Name(req, name)| (req, name))
+                    .collect(),
+            })
+        } else if lookahead.peek(kw::serialize) {
+            let kw = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let value = input.parse()?;
+            Ok(VariantMeta::Serialize { kw, value })
+        } else if lookahead.peek(kw::to_string) {
+            let kw = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let value = input.parse()?;
+            Ok(VariantMeta::ToString { kw, value })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+struct VersionLit(LitStr, LitInt);
+
+impl Parse for VersionLit {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let version = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let value = input.parse()?;
+
+        Ok(VersionLit(version, value))
+    }
+}
+
+struct Name(LitStr, LitStr);
+
+impl Parse for Name {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let req = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let name = input.parse()?;
+
+        Ok(Name(req, name))
+    }
+}
+
+impl Spanned for VariantMeta {
+    fn span(&self) -> Span {
+        match self {
+            VariantMeta::Implemented { kw, .. } => kw.span,
+            VariantMeta::Deprecated { kw, .. } => kw.span,
+            VariantMeta::AlternativeVersion { kw, .. } => kw.span,
+            VariantMeta::AlternativeName { kw, .. } => kw.span,
+            VariantMeta::Serialize { kw, .. } => kw.span,
+            VariantMeta::ToString { kw, .. } => kw.span,
+        }
+    }
+}
+
+pub trait VariantExt {
+    /// Get all the metadata associated with an enum variant.
+    fn get_metadata(&self) -> syn::Result<Vec<VariantMeta>>;
+}
+
+impl VariantExt for Variant {
+    fn get_metadata(&self) -> syn::Result<Vec<VariantMeta>> {
+        get_metadata_inner("multi_version", &self.attrs)
+    }
+}
+
+fn get_metadata_inner<'a, T: Parse + Spanned>(
+    ident: &str,
+    it: impl IntoIterator<Item = &'a Attribute>,
+) -> syn::Result<Vec<T>> {
+    it.into_iter()
+        .filter(|attr| attr.path.is_ident(ident))
+        .try_fold(Vec::new(), |mut vec, attr| {
+            vec.extend(attr.parse_args_with(Punctuated::<T, Token![,]>::parse_terminated)?);
+            Ok(vec)
+        })
+}
+
+pub trait HasMultiVersionVariantProperties {
+    fn get_variant_properties(&self) -> syn::Result<MultiVersionVariantProperties>;
+}
+
+#[derive(Clone, Default)]
+pub struct MultiVersionVariantProperties {
+    pub implemented: Option<LitStr>,
+    pub deprecated: Option<LitStr>,
+    pub alternate_versions: Vec<(LitStr, LitInt)>,
+    pub alternate_names: Vec<(LitStr, LitStr)>,
+    pub serialize: Vec<LitStr>,
+    pub to_string: Option<LitStr>,
+}
+
+impl HasMultiVersionVariantProperties for Variant {
+    fn get_variant_properties(&self) -> syn::Result<MultiVersionVariantProperties> {
+        let mut output = MultiVersionVariantProperties::default();
+
+        let mut implemented_kw = None;
+        let mut deprecated_kw = None;
+        let mut to_string_kw = None;
+        for meta in self.get_metadata()? {
+            match meta {
+                VariantMeta::Implemented { value, kw } => {
+                    if let Some(fst_kw) = implemented_kw {
+                        return Err(occurrence_error(fst_kw, kw, "implemented"));
+                    }
+                    parse_version(&value)?;
+
+                    implemented_kw = Some(kw);
+                    output.implemented = Some(value);
+                }
+                VariantMeta::Deprecated { value, kw } => {
+                    if let Some(fst_kw) = deprecated_kw {
+                        return Err(occurrence_error(fst_kw, kw, "deprecated"));
+                    }
+                    parse_version(&value)?;
+
+                    deprecated_kw = Some(kw);
+                    output.deprecated = Some(value);
+                }
+                VariantMeta::AlternativeVersion { versions, .. } => {
+                    for (req, _) in &versions {
+                        parse_version_req(req)?;
+                    }
+                    output.alternate_versions.extend(versions);
+                }
+                VariantMeta::AlternativeName { names, .. } => {
+                    for (req, _) in &names {
+                        parse_version_req(req)?;
+                    }
+                    output.alternate_names.extend(names);
+                }
+                VariantMeta::Serialize { value, .. } => {
+                    output.serialize.push(value);
+                }
+                VariantMeta::ToString { value, kw } => {
+                    if let Some(fst_kw) = to_string_kw {
+                        return Err(occurrence_error(fst_kw, kw, "to_string"));
+                    }
+
+                    to_string_kw = Some(kw);
+                    output.to_string = Some(value);
+                }
+            }
+        }
+
+        if let (Some(implemented), Some(deprecated)) = (&output.implemented, &output.deprecated) {
+            if parse_version(deprecated)? < parse_version(implemented)? {
+                return Err(version_order_error(
+                    implemented,
+                    deprecated,
+                    "`deprecated` must not be before `implemented`",
+                ));
+            }
+        }
+
+        if let Some(implemented) = &output.implemented {
+            let implemented_version = parse_version(implemented)?;
+
+            for (req, _) in &output.alternate_versions {
+                // Only comparators that establish a lower bound (`=`, `>`,
+                // `>=`, `^`, `~`, wildcard) constrain how low a matched
+                // version can go. A requirement made up solely of `<`/`<=`
+                // comparators has no floor and can match all the way down
+                // to 0.0.0.
+                let lower_bound = parse_version_req(req)?
+                    .comparators
+                    .iter()
+                    .filter_map(|comparator| match comparator.op {
+                        Op::Less | Op::LessEq => None,
+                        _ => Some(Version::new(
+                            comparator.major,
+                            comparator.minor.unwrap_or(0),
+                            comparator.patch.unwrap_or(0),
+                        )),
+                    })
+                    .min()
+                    .unwrap_or_else(|| Version::new(0, 0, 0));
+
+                if lower_bound < implemented_version {
+                    return Err(version_order_error(
+                        implemented,
+                        req,
+                        "`alternative_version` must not reference a version below `implemented`",
+                    ));
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// Parses a `LitStr` as a [`semver::Version`], reporting malformed
+/// literals as a compile error spanned at the literal rather than an
+/// `unwrap` panic in the generated code.
+fn parse_version(lit: &LitStr) -> syn::Result<Version> {
+    Version::from_str(&lit.value())
+        .map_err(|err| syn::Error::new_spanned(lit, format!("invalid semver version: {}", err)))
+}
+
+/// Parses a `LitStr` as a [`semver::VersionReq`], reporting malformed
+/// literals as a compile error spanned at the literal rather than an
+/// `unwrap` panic in the generated code.
+fn parse_version_req(lit: &LitStr) -> syn::Result<VersionReq> {
+    VersionReq::from_str(&lit.value()).map_err(|err| {
+        syn::Error::new_spanned(lit, format!("invalid semver version requirement: {}", err))
+    })
+}
+
+pub fn occurrence_error<T: quote::ToTokens>(fst: T, snd: T, attr: &str) -> syn::Error {
+    let mut e = syn::Error::new_spanned(
+        snd,
+        format!("Found multiple occurrences of multi_version({})", attr),
+    );
+    e.combine(syn::Error::new_spanned(fst, "first one here"));
+    e
+}
+
+/// Like [`occurrence_error`], but for two literals that violate a version
+/// ordering invariant rather than being a duplicate attribute.
+fn version_order_error<T: quote::ToTokens>(fst: T, snd: T, message: &str) -> syn::Error {
+    let mut e = syn::Error::new_spanned(snd, message);
+    e.combine(syn::Error::new_spanned(fst, "implemented here"));
+    e
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(src: &str) -> Variant {
+        syn::parse_str(src).expect("failed to parse test variant")
+    }
+
+    #[test]
+    fn malformed_implemented_literal_is_a_spanned_error() {
+        let v = variant(r#"#[multi_version(implemented = "1.x")] Foo"#);
+        assert!(v.get_variant_properties().is_err());
+    }
+
+    #[test]
+    fn malformed_alternative_version_requirement_is_a_spanned_error() {
+        let v = variant(r#"#[multi_version(alternative_version("not-a-version", 5))] Foo"#);
+        assert!(v.get_variant_properties().is_err());
+    }
+
+    #[test]
+    fn deprecated_before_implemented_is_rejected() {
+        let v = variant(
+            r#"#[multi_version(implemented = "1.0.0", deprecated = "0.5.0")] Foo"#,
+        );
+        assert!(v.get_variant_properties().is_err());
+    }
+
+    #[test]
+    fn deprecated_after_implemented_is_accepted() {
+        let v = variant(
+            r#"#[multi_version(implemented = "0.5.0", deprecated = "1.0.0")] Foo"#,
+        );
+        assert!(v.get_variant_properties().is_ok());
+    }
+
+    #[test]
+    fn unbounded_below_alternative_version_is_rejected() {
+        // A requirement made up only of `<` comparators has no lower
+        // bound, so it can match versions before `implemented` even
+        // though no individual comparator literal is below it.
+        let v = variant(
+            r#"#[multi_version(implemented = "0.5.0", alternative_version("<1.0.0", 5))] Foo"#,
+        );
+        assert!(v.get_variant_properties().is_err());
+    }
+
+    #[test]
+    fn alternative_version_with_floor_at_or_above_implemented_is_accepted() {
+        let v = variant(
+            r#"#[multi_version(implemented = "0.5.0", alternative_version(">=0.6.0", 5))] Foo"#,
+        );
+        assert!(v.get_variant_properties().is_ok());
+    }
+}